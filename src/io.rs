@@ -0,0 +1,133 @@
+//! Byte-oriented `embedded-io` `Read`/`Write` implementations, available
+//! behind the `io` feature.
+//!
+//! `WheelBuf<C, u8>` gets `Write` so it can act as a drop-in overwriting
+//! byte sink (loggers, UART capture). `WheelBuf` keeps no read cursor (see
+//! the crate docs), so it cannot satisfy `Read` faithfully: a `Read` would
+//! never consume data and so never report `Ok(0)`, spinning forever in a
+//! typical read-until-empty loop. `SyncWheelBuf`'s [`Consumer`] *can*
+//! satisfy `Read`, since popping advances `head`, so that is where `Read`
+//! lives instead.
+
+use core::convert::Infallible;
+
+use embedded_io::{ErrorType, Read, Write};
+
+use crate::sync::Consumer;
+use crate::WheelBuf;
+
+impl<C> ErrorType for WheelBuf<C, u8>
+where
+    C: AsMut<[u8]> + AsRef<[u8]>,
+{
+    type Error = Infallible;
+}
+
+impl<C> Write for WheelBuf<C, u8>
+where
+    C: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Pushes `buf` onto the wheel buffer via the bulk `extend_from_slice`
+    /// path. Always succeeds, overwriting the oldest bytes if `buf` is
+    /// larger than the remaining capacity.
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, C> ErrorType for Consumer<'a, C, u8>
+where
+    C: AsMut<[u8]> + AsRef<[u8]>,
+{
+    type Error = Infallible;
+}
+
+impl<'a, C> Read for Consumer<'a, C, u8>
+where
+    C: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Drains up to `buf.len()` of the oldest bytes into `buf` by popping
+    /// one at a time, returning how many were copied. Unlike a plain
+    /// `WheelBuf`, this actually consumes the bytes: once the buffer is
+    /// empty this returns `Ok(0)`, so a normal read-until-empty loop
+    /// terminates.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut n = 0;
+
+        while n < buf.len() {
+            match self.pop() {
+                Some(byte) => {
+                    buf[n] = byte;
+                    n += 1;
+                }
+                None => break,
+            }
+        }
+
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyncWheelBuf;
+
+    #[test]
+    fn write_then_read_drains() {
+        let mut sync_buf: SyncWheelBuf<[u8; 8], u8> = SyncWheelBuf::new([0; 8]);
+        let (mut producer, mut consumer) = sync_buf.split();
+
+        for &byte in b"Hello" {
+            producer.push(byte).unwrap();
+        }
+
+        let mut out = [0u8; 8];
+        let n = Read::read(&mut consumer, &mut out).unwrap();
+        assert_eq!(n, 5);
+        assert_eq!(&out[..5], b"Hello");
+
+        // The bytes were consumed: reading again yields nothing.
+        assert_eq!(Read::read(&mut consumer, &mut out).unwrap(), 0);
+    }
+
+    #[test]
+    fn read_into_smaller_buffer_then_drains_rest() {
+        let mut sync_buf: SyncWheelBuf<[u8; 8], u8> = SyncWheelBuf::new([0; 8]);
+        let (mut producer, mut consumer) = sync_buf.split();
+
+        for &byte in b"Hello" {
+            producer.push(byte).unwrap();
+        }
+
+        let mut out = [0u8; 3];
+        let n = Read::read(&mut consumer, &mut out).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(&out, b"Hel");
+
+        let n = Read::read(&mut consumer, &mut out).unwrap();
+        assert_eq!(n, 2);
+        assert_eq!(&out[..2], b"lo");
+    }
+
+    #[test]
+    fn wheel_buf_write_overwrites() {
+        let mut buf = [0u8; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        Write::write(&mut wheel, b"Hello World").unwrap();
+
+        let (first, second) = wheel.as_slices();
+        let mut s = [0u8; 8];
+        s[..first.len()].copy_from_slice(first);
+        s[first.len()..].copy_from_slice(second);
+        assert_eq!(&s, b"lo World");
+    }
+}
@@ -26,10 +26,20 @@
 
 #![no_std]
 
+mod sync;
+pub use sync::{Consumer, Error, Producer, SyncWheelBuf};
+
+#[cfg(feature = "io")]
+mod io;
+
 use core::cmp;
 use core::convert::AsRef;
 use core::marker::PhantomData;
 use core::fmt::Write;
+use core::iter;
+use core::iter::ExactSizeIterator;
+use core::ops::{Index, IndexMut};
+use core::slice;
 
 /// A multi-read Ringbuffer.
 ///
@@ -61,6 +71,13 @@ where
 {
     buffer: &'a WheelBuf<C, I>,
     cur: usize,
+    end: usize,
+}
+
+/// `WheelBuf` mutable iterator.
+#[derive(Debug)]
+pub struct WheelBufIterMut<'a, I: 'a> {
+    inner: iter::Chain<slice::IterMut<'a, I>, slice::IterMut<'a, I>>,
 }
 
 impl<C, I> WheelBuf<C, I>
@@ -120,11 +137,86 @@ where
 
     /// Creates an iterator over buffer.
     #[inline]
-    pub fn iter(&self) -> WheelBufIter<C, I> {
+    pub fn iter(&self) -> WheelBufIter<'_, C, I> {
         WheelBufIter {
             buffer: self,
             cur: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Creates a mutable iterator over the buffer.
+    #[inline]
+    pub fn iter_mut(&mut self) -> WheelBufIterMut<'_, I> {
+        let (first, second) = self.as_mut_slices();
+
+        WheelBufIterMut {
+            inner: first.iter_mut().chain(second.iter_mut()),
+        }
+    }
+
+    /// Returns the buffer contents as two slices.
+    ///
+    /// The first slice holds the oldest items, running from the read start up
+    /// to the physical end of the backing store (or, while the buffer has not
+    /// yet filled up, up to the insert position); the second slice holds the
+    /// remaining (newer) items that wrapped around to the start of the
+    /// backing store. The second slice is empty unless the buffer has
+    /// wrapped, which lets callers copy the whole window in at most two
+    /// `copy_from_slice` calls instead of going through `iter()`.
+    #[inline]
+    pub fn as_slices(&self) -> (&[I], &[I]) {
+        let data = self.data.as_ref();
+
+        if self.total < self.capacity() {
+            (&data[..self.pos], &[])
+        } else {
+            (&data[self.pos..], &data[..self.pos])
+        }
+    }
+
+    /// Returns the buffer contents as two mutable slices.
+    ///
+    /// See [`as_slices`](#method.as_slices) for the splitting rules.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [I], &mut [I]) {
+        let wrapped = self.total >= self.capacity();
+        let pos = self.pos;
+        let data = self.data.as_mut();
+
+        if wrapped {
+            let (second, first) = data.split_at_mut(pos);
+            (first, second)
+        } else {
+            let (head, _) = data.split_at_mut(pos);
+            (head, &mut [])
+        }
+    }
+
+    /// Returns the `n`-th oldest item in the buffer.
+    ///
+    /// `0` is the oldest item still held, `len() - 1` the most recently
+    /// pushed one. Returns `None` if `n >= len()`.
+    #[inline]
+    pub fn get(&self, n: usize) -> Option<&I> {
+        if n >= self.len() {
+            return None;
         }
+
+        Some(&self.data.as_ref()[(self.read_start() + n) % self.capacity()])
+    }
+
+    /// Returns a mutable reference to the `n`-th oldest item in the buffer.
+    ///
+    /// See [`get`](#method.get) for the indexing rules.
+    #[inline]
+    pub fn get_mut(&mut self, n: usize) -> Option<&mut I> {
+        if n >= self.len() {
+            return None;
+        }
+
+        let idx = (self.read_start() + n) % self.capacity();
+        Some(&mut self.data.as_mut()[idx])
     }
 
     #[inline]
@@ -133,6 +225,52 @@ where
     }
 }
 
+impl<C, I> WheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+    I: Copy,
+{
+    /// Adds a batch of items to the wheel buffer.
+    ///
+    /// Equivalent to calling [`push`](#method.push) for every item in
+    /// `items`, but performs the insert in at most two `copy_from_slice`
+    /// calls instead of one scalar store per item. If `items` is longer than
+    /// the buffer's capacity, only the last `capacity` items are kept, since
+    /// the rest would be overwritten anyway.
+    pub fn extend_from_slice(&mut self, items: &[I]) {
+        let capacity = self.capacity();
+
+        if capacity == 0 {
+            self.total += items.len();
+            return;
+        }
+
+        if items.len() >= capacity {
+            let tail = &items[items.len() - capacity..];
+            let pos = self.pos;
+            Self::copy_in(self.data.as_mut(), pos, tail);
+            self.total += items.len();
+        } else {
+            let pos = self.pos;
+            Self::copy_in(self.data.as_mut(), pos, items);
+            self.pos = (pos + items.len()) % capacity;
+            self.total += items.len();
+        }
+    }
+
+    /// Copies `items` into `data` starting at `start`, wrapping around the
+    /// end of `data` if necessary. Assumes `items.len() <= data.len()`.
+    #[inline]
+    fn copy_in(data: &mut [I], start: usize, items: &[I]) {
+        let first_len = cmp::min(items.len(), data.len() - start);
+        data[start..start + first_len].copy_from_slice(&items[..first_len]);
+
+        if first_len < items.len() {
+            data[..items.len() - first_len].copy_from_slice(&items[first_len..]);
+        }
+    }
+}
+
 impl<'a, C, I> Iterator for WheelBufIter<'a, C, I>
 where
     C: AsMut<[I]> + AsRef<[I]>,
@@ -143,24 +281,111 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cur >= self.buffer.len() {
+        if self.cur >= self.end {
             return None;
         }
 
         let cur = self.cur;
         self.cur += 1;
-        Some(&self.buffer.data.as_ref()[(self.buffer.read_start() + cur) % self.buffer.capacity()])
+        self.buffer.get(cur)
     }
 
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let max_idx = cmp::min(self.buffer.total, self.buffer.capacity());
+        let idx = self.cur + n;
+
+        if idx >= self.end {
+            self.cur = self.end;
+            return None;
+        }
+
+        self.cur = idx + 1;
+        self.buffer.get(idx)
+    }
 
-        if n > 0 {
-            self.cur += cmp::min(n, max_idx);
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, C, I> DoubleEndedIterator for WheelBufIter<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+    I: 'a,
+    C: 'a,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cur >= self.end {
+            return None;
         }
 
-        self.next()
+        self.end -= 1;
+        self.buffer.get(self.end)
+    }
+}
+
+impl<'a, C, I> ExactSizeIterator for WheelBufIter<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+    I: 'a,
+    C: 'a,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.cur
+    }
+}
+
+impl<'a, I> Iterator for WheelBufIterMut<'a, I> {
+    type Item = &'a mut I;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, I> DoubleEndedIterator for WheelBufIterMut<'a, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, I> ExactSizeIterator for WheelBufIterMut<'a, I> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.size_hint().0
+    }
+}
+
+impl<C, I> Index<usize> for WheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    type Output = I;
+
+    #[inline]
+    fn index(&self, n: usize) -> &I {
+        self.get(n).expect("index out of bounds")
+    }
+}
+
+impl<C, I> IndexMut<usize> for WheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    #[inline]
+    fn index_mut(&mut self, n: usize) -> &mut I {
+        self.get_mut(n).expect("index out of bounds")
     }
 }
 
@@ -184,6 +409,7 @@ extern crate std;
 mod tests {
     use core::fmt::Write;
     use std::string::String;
+    use std::vec::Vec;
     use super::*;
 
     #[test]
@@ -236,6 +462,177 @@ mod tests {
         assert_eq!(s.as_str(), "rld! 123");
     }
 
+    #[test]
+    fn as_slices_unwrapped() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        wheel.push('H');
+        wheel.push('e');
+        wheel.push('l');
+
+        let (first, second) = wheel.as_slices();
+        assert_eq!(first, &['H', 'e', 'l']);
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn as_slices_wrapped() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        let (first, second) = wheel.as_slices();
+        let mut s = String::new();
+        s.extend(first.iter());
+        s.extend(second.iter());
+        assert_eq!(s.as_str(), "lo World");
+    }
+
+    #[test]
+    fn as_mut_slices_wrapped() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        {
+            let (first, second) = wheel.as_mut_slices();
+            for c in first.iter_mut().chain(second.iter_mut()) {
+                *c = c.to_ascii_uppercase();
+            }
+        }
+
+        let s: String = wheel.iter().cloned().collect();
+        assert_eq!(s.as_str(), "LO WORLD");
+    }
+
+    #[test]
+    fn extend_from_slice_partial() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        wheel.push('H');
+        wheel.extend_from_slice(&['e', 'l', 'l', 'o']);
+        assert_eq!(wheel.len(), 5);
+
+        let s: String = wheel.iter().cloned().collect();
+        assert_eq!(s.as_str(), "Hello");
+    }
+
+    #[test]
+    fn extend_from_slice_matches_push() {
+        let mut pushed_buf = ['x'; 8];
+        let mut pushed = WheelBuf::new(&mut pushed_buf);
+        let mut extended_buf = ['x'; 8];
+        let mut extended = WheelBuf::new(&mut extended_buf);
+
+        let items: Vec<char> = "Hello, World!".chars().collect();
+
+        for &c in &items {
+            pushed.push(c);
+        }
+        extended.extend_from_slice(&items);
+
+        assert_eq!(pushed.total(), extended.total());
+        assert!(pushed.iter().eq(extended.iter()));
+    }
+
+    #[test]
+    fn get_and_index() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        assert_eq!(*wheel.get(0).unwrap(), 'l');
+        assert_eq!(wheel[0], 'l');
+        assert_eq!(wheel[7], 'd');
+        assert!(wheel.get(8).is_none());
+    }
+
+    #[test]
+    fn get_mut_and_index_mut() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        *wheel.get_mut(0).unwrap() = 'L';
+        wheel[7] = 'D';
+
+        let s: String = wheel.iter().cloned().collect();
+        assert_eq!(s.as_str(), "Lo WorlD");
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let mut buf = ['x'; 8];
+        let wheel: WheelBuf<_, char> = WheelBuf::new(&mut buf);
+        let _ = wheel[0];
+    }
+
+    #[test]
+    fn iter_exact_size() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        wheel.push('H');
+        wheel.push('e');
+        wheel.push('l');
+
+        assert_eq!(wheel.iter().len(), 3);
+        assert_eq!(wheel.iter().size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn iter_double_ended() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        let s: String = wheel.iter().rev().cloned().collect();
+        assert_eq!(s.as_str(), "dlroW ol");
+
+        let mut it = wheel.iter();
+        assert_eq!(*it.next().unwrap(), 'l');
+        assert_eq!(*it.next_back().unwrap(), 'd');
+        assert_eq!(it.len(), 6);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut buf = ['x'; 8];
+        let mut wheel = WheelBuf::new(&mut buf);
+
+        for c in "Hello World".chars() {
+            wheel.push(c);
+        }
+
+        for c in wheel.iter_mut() {
+            *c = c.to_ascii_uppercase();
+        }
+
+        let s: String = wheel.iter().cloned().collect();
+        assert_eq!(s.as_str(), "LO WORLD");
+
+        assert_eq!(wheel.iter_mut().len(), 8);
+        assert_eq!(*wheel.iter_mut().next_back().unwrap(), 'D');
+    }
+
     #[test]
     fn using_vec() {
         let mut buf = vec!['x', 'x', 'x', 'x', 'x', 'x', 'x', 'x'];
@@ -0,0 +1,381 @@
+//! Lock-free single-producer/single-consumer ring buffer.
+//!
+//! Unlike [`WheelBuf`](crate::WheelBuf), [`SyncWheelBuf`] never silently
+//! overwrites unread data: a full buffer makes `push` fail instead. It is
+//! meant for handing data off between an interrupt handler and the main
+//! loop (or any other single producer/single consumer pair) without a lock,
+//! which is why its constructor is a `const fn` and it can live in a
+//! `static`.
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Errors produced by [`SyncWheelBuf`] operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer has no free slot; the producer must wait for the consumer
+    /// to `pop` before it can `push` again.
+    BufferFull,
+}
+
+/// A lock-free single-producer/single-consumer ring buffer.
+///
+/// `head` and `tail` are stored modulo `2 * capacity`, so `head == tail`
+/// means the buffer is empty and a difference of `capacity` means it is
+/// full, without needing a separate flag. The consumer only ever advances
+/// `head` with `Acquire`/`Release` ordering on the index the producer reads,
+/// and vice versa, so the two sides never need to block each other.
+///
+/// `push`/`pop` (and the `capacity`/`len`/`is_empty`/`is_full` queries that
+/// go with them) live on the [`Producer`]/[`Consumer`] halves handed out by
+/// [`split`](SyncWheelBuf::split), so the single-producer/single-consumer
+/// invariant is enforced by the borrow checker rather than left to a doc
+/// comment: `split` takes `&mut self`, so only one `Producer` and one
+/// `Consumer` can ever exist at a time.
+///
+/// `split` is also the only place that ever reaches into the `UnsafeCell`
+/// through a reference (`&mut self` there is still exclusive, so that is
+/// sound); it caches the backing store's length and a raw pointer to its
+/// first element. From then on `push`/`pop` index through that cached raw
+/// pointer with `ptr::write`/`ptr::read`, never materializing a `&mut
+/// [I]`/`&[I]` spanning the whole container. That matters because the
+/// producer and consumer can run on different threads at the same instant:
+/// forming a whole-container reference on one side while the other side
+/// holds a conflicting reference to the same allocation is aliasing
+/// undefined behavior even when the two sides only ever touch disjoint
+/// indices.
+pub struct SyncWheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    data: UnsafeCell<C>,
+    /// Raw pointer to `data`'s first element, cached by `split`. Null until
+    /// `split` has run; `push_impl`/`pop_impl` are unreachable until then,
+    /// since they are only ever called through a `Producer`/`Consumer`.
+    data_ptr: *mut I,
+    /// `data`'s length, cached by `split` alongside `data_ptr` for the same
+    /// reason.
+    capacity: usize,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    _pd: PhantomData<I>,
+}
+
+unsafe impl<C, I> Sync for SyncWheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]> + Send,
+    I: Send,
+{
+}
+
+impl<C, I> fmt::Debug for SyncWheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SyncWheelBuf")
+            .field("head", &self.head.load(Ordering::Relaxed))
+            .field("tail", &self.tail.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Wraps `idx` (which lives in `0..2*capacity`) down into `0..capacity`.
+#[inline]
+fn physical(idx: usize, capacity: usize) -> usize {
+    if idx >= capacity {
+        idx - capacity
+    } else {
+        idx
+    }
+}
+
+/// Advances `idx` by one slot, wrapping at `2*capacity`.
+#[inline]
+fn advance(idx: usize, capacity: usize) -> usize {
+    if idx + 1 >= 2 * capacity {
+        0
+    } else {
+        idx + 1
+    }
+}
+
+/// Number of items between `head` and `tail`, both taken modulo `2*capacity`.
+#[inline]
+fn diff(tail: usize, head: usize, capacity: usize) -> usize {
+    (tail + 2 * capacity - head) % (2 * capacity)
+}
+
+impl<C, I> SyncWheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    /// Creates a new `SyncWheelBuf`.
+    ///
+    /// `data` is a backing data structure that must be convertible into a
+    /// slice; its `len()` determines the capacity, cached the first time
+    /// [`split`](Self::split) runs. Since this is a `const fn`, a
+    /// `SyncWheelBuf` can be placed directly in a `static`.
+    #[inline]
+    pub const fn new(data: C) -> SyncWheelBuf<C, I> {
+        SyncWheelBuf {
+            data: UnsafeCell::new(data),
+            data_ptr: ptr::null_mut(),
+            capacity: 0,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Splits the buffer into its producer and consumer halves.
+    ///
+    /// Taking `&mut self` means a `Producer`/`Consumer` pair can only be
+    /// obtained once the caller already has exclusive access to the buffer
+    /// (e.g. right after constructing it, or via `static mut` plus
+    /// `unsafe` in embedded code); from then on `push`/`pop` are only
+    /// reachable through the returned halves, which are not `Clone`, so the
+    /// single-producer/single-consumer invariant holds structurally instead
+    /// of by convention. It's also the only place that reads `data`'s
+    /// length and element pointer through the `UnsafeCell` -- `&mut self`
+    /// here is still exclusive, so doing it once now means `push_impl`/
+    /// `pop_impl` never need to reach through the cell again.
+    #[inline]
+    pub fn split(&mut self) -> (Producer<'_, C, I>, Consumer<'_, C, I>) {
+        let data = self.data.get_mut();
+        self.capacity = data.as_ref().len();
+        self.data_ptr = data.as_mut().as_mut_ptr();
+        (Producer { buf: self }, Consumer { buf: self })
+    }
+
+    /// Returns a raw pointer to the (already in-bounds) slot `idx`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must only dereference the result while `idx` is a slot
+    /// the producer/consumer protocol has established is theirs to touch
+    /// (i.e. the checks in `push_impl`/`pop_impl`), and must access it
+    /// through `ptr::write`/`ptr::read` rather than forming a reference
+    /// that spans more than that one slot.
+    #[inline]
+    unsafe fn slot_ptr(&self, idx: usize) -> *mut I {
+        self.data_ptr.add(idx)
+    }
+
+    fn is_full_impl(&self) -> bool {
+        self.capacity == 0
+            || diff(
+                self.tail.load(Ordering::Acquire),
+                self.head.load(Ordering::Acquire),
+                self.capacity,
+            ) == self.capacity
+    }
+
+    fn is_empty_impl(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    fn len_impl(&self) -> usize {
+        if self.capacity == 0 {
+            return 0;
+        }
+
+        diff(
+            self.tail.load(Ordering::Acquire),
+            self.head.load(Ordering::Acquire),
+            self.capacity,
+        )
+    }
+
+    fn push_impl(&self, item: I) -> Result<(), Error> {
+        if self.capacity == 0 {
+            return Err(Error::BufferFull);
+        }
+
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+
+        if diff(tail, head, self.capacity) == self.capacity {
+            return Err(Error::BufferFull);
+        }
+
+        let idx = physical(tail, self.capacity);
+        // SAFETY: `idx` is empty (checked above) and is the producer's own
+        // slot -- `pop_impl` only ever reads slots between `head` and
+        // `tail`, which this one is not until `tail` is advanced below.
+        unsafe { ptr::write(self.slot_ptr(idx), item) };
+
+        self.tail.store(advance(tail, self.capacity), Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<C, I> SyncWheelBuf<C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+    I: Copy,
+{
+    fn pop_impl(&self) -> Option<I> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        let idx = physical(head, self.capacity);
+        // SAFETY: `idx` is occupied (checked above) and is the consumer's
+        // own slot -- `push_impl` only ever writes slots between `tail` and
+        // `head + capacity`, which this one is not until `head` is advanced
+        // below.
+        let item = unsafe { ptr::read(self.slot_ptr(idx)) };
+        self.head.store(advance(head, self.capacity), Ordering::Release);
+        Some(item)
+    }
+}
+
+/// Producer half of a [`SyncWheelBuf`], obtained via [`SyncWheelBuf::split`].
+///
+/// Only this half can `push`, and only one can exist at a time, so it is
+/// safe to hand to the single producer thread (or interrupt handler).
+pub struct Producer<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    buf: &'a SyncWheelBuf<C, I>,
+}
+
+impl<'a, C, I> Producer<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    /// Pushes an item onto the buffer.
+    ///
+    /// Returns `Err(Error::BufferFull)` without touching the buffer if
+    /// there is no free slot, rather than overwriting unread data the way
+    /// [`WheelBuf::push`](crate::WheelBuf::push) does.
+    #[inline]
+    pub fn push(&mut self, item: I) -> Result<(), Error> {
+        self.buf.push_impl(item)
+    }
+
+    /// Returns `true` if the buffer has no free slot left.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.buf.is_full_impl()
+    }
+
+    /// Capacity of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity
+    }
+}
+
+/// Consumer half of a [`SyncWheelBuf`], obtained via [`SyncWheelBuf::split`].
+///
+/// Only this half can `pop`, and only one can exist at a time, so it is
+/// safe to hand to the single consumer thread (or main loop).
+pub struct Consumer<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+{
+    buf: &'a SyncWheelBuf<C, I>,
+}
+
+impl<'a, C, I> Consumer<'a, C, I>
+where
+    C: AsMut<[I]> + AsRef<[I]>,
+    I: Copy,
+{
+    /// Pops the oldest item off the buffer, or returns `None` if it is
+    /// empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<I> {
+        self.buf.pop_impl()
+    }
+
+    /// Returns `true` if the buffer holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty_impl()
+    }
+
+    /// Number of items currently held in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len_impl()
+    }
+
+    /// Capacity of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop_roundtrip() {
+        let mut buf: SyncWheelBuf<[u8; 4], u8> = SyncWheelBuf::new([0; 4]);
+        let (mut p, mut c) = buf.split();
+
+        assert!(c.is_empty());
+        p.push(1).unwrap();
+        p.push(2).unwrap();
+
+        assert_eq!(c.pop(), Some(1));
+        assert_eq!(c.pop(), Some(2));
+        assert_eq!(c.pop(), None);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn push_rejects_when_full() {
+        let mut buf: SyncWheelBuf<[u8; 2], u8> = SyncWheelBuf::new([0; 2]);
+        let (mut p, mut c) = buf.split();
+
+        p.push(1).unwrap();
+        p.push(2).unwrap();
+        assert!(p.is_full());
+        assert_eq!(p.push(3), Err(Error::BufferFull));
+
+        assert_eq!(c.pop(), Some(1));
+        assert!(!p.is_full());
+        p.push(3).unwrap();
+        assert_eq!(c.pop(), Some(2));
+        assert_eq!(c.pop(), Some(3));
+    }
+
+    #[test]
+    fn wraps_around_many_cycles() {
+        let mut buf: SyncWheelBuf<[u8; 3], u8> = SyncWheelBuf::new([0; 3]);
+        let (mut p, mut c) = buf.split();
+
+        for round in 0..10u8 {
+            p.push(round).unwrap();
+            p.push(round.wrapping_add(100)).unwrap();
+            assert_eq!(c.pop(), Some(round));
+            assert_eq!(c.pop(), Some(round.wrapping_add(100)));
+        }
+    }
+
+    #[test]
+    fn const_new_in_static() {
+        static mut BUF: SyncWheelBuf<[u8; 4], u8> = SyncWheelBuf::new([0; 4]);
+
+        // `static mut` access requires `unsafe`; this is the usual embedded
+        // idiom for getting an `&'static mut` to split once at startup.
+        #[allow(static_mut_refs)]
+        let (mut p, mut c) = unsafe { BUF.split() };
+
+        p.push(42).unwrap();
+        assert_eq!(c.pop(), Some(42));
+    }
+}